@@ -1,4 +1,8 @@
-use std::{fmt, str::FromStr};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 use crate::{
     error::ParseError, is_valid_domain_segment, is_valid_nsid_name, is_valid_tld, SEGMENT_LEN_RANGE,
@@ -12,13 +16,196 @@ const MIN_SEGMENTS: usize = 3;
 pub struct Nsid(String);
 
 impl Nsid {
+    /// Borrows this NSID as a zero-copy [`NsidRef`].
+    #[inline]
+    pub fn as_ref(&self) -> NsidRef<'_> {
+        NsidRef(self.0.as_str())
+    }
+
+    #[inline]
     pub fn as_str(&self) -> &str {
-        self.0.as_str()
+        self.as_ref().as_str()
     }
 
     pub fn segments(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.as_ref().segments()
+    }
+
+    /// Parses `s`, returning a structured [`NsidError`] on failure instead
+    /// of the opaque [`ParseError`] returned by [`FromStr::from_str`].
+    pub fn parse_detailed(s: &str) -> Result<Nsid, NsidError> {
+        validate_nsid(s.as_bytes()).map(|()| Nsid(s.into()))
+    }
+
+    /// The authority, i.e. every segment but the last.
+    ///
+    /// For `com.example.fooBar`, this is `com.example`.
+    #[inline]
+    pub fn authority(&self) -> &str {
+        self.as_ref().authority()
+    }
+
+    /// The name, i.e. the final segment.
+    ///
+    /// For `com.example.fooBar`, this is `fooBar`.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    /// The authority as an un-reversed DNS domain name.
+    ///
+    /// For `com.example.fooBar`, this is `example.com`.
+    #[inline]
+    pub fn domain_authority(&self) -> String {
+        self.as_ref().domain_authority()
+    }
+
+    /// Assembles and validates an NSID from its authority and name parts.
+    ///
+    /// `name` must be a single segment; a `name` containing a `.` (which
+    /// would otherwise be silently reinterpreted as extending the
+    /// authority) is rejected.
+    pub fn from_parts(authority: &str, name: &str) -> Result<Nsid, ParseError> {
+        if !is_valid_nsid_name(name.as_bytes()) {
+            return Err(ParseError::nsid());
+        }
+
+        Nsid::from_str(&format!("{authority}.{name}"))
+    }
+
+    /// Returns a copy of this NSID with its authority segments lowercased.
+    ///
+    /// The atproto NSID authority maps to a DNS name and is therefore
+    /// case-insensitive, while the final name segment is case-sensitive and
+    /// is left untouched. Use this (or [`Nsid::canonical_eq`]) when two
+    /// NSIDs that differ only in authority casing should compare equal.
+    pub fn normalize(&self) -> Nsid {
+        let authority = self.authority().to_ascii_lowercase();
+        let name = self.name();
+        Nsid(format!("{authority}.{name}"))
+    }
+
+    /// Returns whether `self` and `other` are equal once their authority
+    /// segments are lowercased.
+    pub fn canonical_eq(&self, other: &Nsid) -> bool {
+        self.normalize() == other.normalize()
+    }
+}
+
+/// A wrapper around [`Nsid`] whose [`Eq`] and [`Hash`] impls compare and hash
+/// the authority case-insensitively, per [`Nsid::canonical_eq`].
+///
+/// [`Nsid`] itself derives `Eq`/`Hash` over its raw string, so
+/// `Com.Example.fooBar` and `com.example.fooBar` are distinct keys in a
+/// `HashMap<Nsid, _>`. Wrap NSIDs in `CanonicalNsid` when they need to be
+/// deduplicated or looked up regardless of authority casing; the original
+/// casing is preserved in the wrapped value.
+#[derive(Clone, Debug)]
+pub struct CanonicalNsid(Nsid);
+
+impl CanonicalNsid {
+    #[inline]
+    pub fn as_nsid(&self) -> &Nsid {
+        &self.0
+    }
+
+    #[inline]
+    pub fn into_nsid(self) -> Nsid {
+        self.0
+    }
+}
+
+impl From<Nsid> for CanonicalNsid {
+    #[inline]
+    fn from(nsid: Nsid) -> Self {
+        CanonicalNsid(nsid)
+    }
+}
+
+impl PartialEq for CanonicalNsid {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.canonical_eq(&other.0)
+    }
+}
+
+impl Eq for CanonicalNsid {}
+
+impl Hash for CanonicalNsid {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.normalize().hash(state);
+    }
+}
+
+/// A borrowed, validated NSID.
+///
+/// This is the zero-copy counterpart to [`Nsid`]: it runs the same
+/// validation as [`Nsid::from_str`], but holds a `&str` slice rather than an
+/// owned `String`. Prefer this when validating NSIDs borrowed from a larger
+/// document (e.g. while walking a lexicon file) where allocating a `String`
+/// per reference would be wasteful.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NsidRef<'a>(&'a str);
+
+impl<'a> NsidRef<'a> {
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = &'a str> {
         self.0.split('.')
     }
+
+    /// The authority, i.e. every segment but the last.
+    ///
+    /// For `com.example.fooBar`, this is `com.example`.
+    pub fn authority(&self) -> &'a str {
+        self.0
+            .rsplit_once('.')
+            .expect("validated NSID has at least one '.'")
+            .0
+    }
+
+    /// The name, i.e. the final segment.
+    ///
+    /// For `com.example.fooBar`, this is `fooBar`.
+    pub fn name(&self) -> &'a str {
+        self.0
+            .rsplit_once('.')
+            .expect("validated NSID has at least one '.'")
+            .1
+    }
+
+    /// The authority as an un-reversed DNS domain name.
+    ///
+    /// For `com.example.fooBar`, this is `example.com`.
+    pub fn domain_authority(&self) -> String {
+        self.authority().split('.').rev().collect::<Vec<_>>().join(".")
+    }
+
+    /// Allocates an owned [`Nsid`] with the same contents.
+    pub fn to_owned(&self) -> Nsid {
+        Nsid(self.0.to_string())
+    }
+}
+
+impl<'a> TryFrom<&'a str> for NsidRef<'a> {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        validate_nsid(s.as_bytes())
+            .map(|()| NsidRef(s))
+            .map_err(ParseError::from)
+    }
+}
+
+impl fmt::Display for NsidRef<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
 }
 
 impl fmt::Display for Nsid {
@@ -32,7 +219,9 @@ impl FromStr for Nsid {
 
     #[inline]
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        validate_nsid(s.as_bytes()).map(|()| Nsid(s.into()))
+        validate_nsid(s.as_bytes())
+            .map(|()| Nsid(s.into()))
+            .map_err(ParseError::from)
     }
 }
 
@@ -41,41 +230,203 @@ impl TryFrom<&'_ [u8]> for Nsid {
 
     #[inline]
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-        validate_nsid(bytes).map(|()| Nsid(String::from_utf8(bytes.into()).unwrap()))
+        validate_nsid(bytes)
+            .map(|()| Nsid(String::from_utf8(bytes.into()).unwrap()))
+            .map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<String> for Nsid {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        validate_nsid(s.as_bytes())
+            .map(|()| Nsid(s))
+            .map_err(ParseError::from)
     }
 }
 
-fn validate_nsid(bytes: &[u8]) -> Result<(), ParseError> {
+#[cfg(feature = "serde")]
+impl serde::Serialize for Nsid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Nsid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Nsid::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The reason an [`Nsid`] or [`Fragment`] failed to validate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NsidErrorKind {
+    /// The input exceeds [`MAX_LEN`] bytes.
+    TooLong,
+    /// The input has fewer than [`MIN_SEGMENTS`] dot-separated segments.
+    TooFewSegments,
+    /// The leading segment is not a valid top-level domain.
+    InvalidTld,
+    /// A non-final authority segment contains characters not permitted in a
+    /// domain segment.
+    InvalidAuthoritySegment,
+    /// The authority (every segment but the last) is [`MAX_AUTHORITY_LEN`]
+    /// bytes or longer.
+    AuthorityTooLong,
+    /// The final segment is not a valid NSID name.
+    InvalidNameSegment,
+    /// A fragment character is not an ASCII alphanumeric.
+    NonAsciiAlphanumeric,
+    /// The fragment is missing its leading `#`.
+    MissingFragmentPrefix,
+    /// The fragment's length (excluding the leading `#`) is out of range.
+    InvalidFragmentLength,
+}
+
+/// A structured NSID or fragment validation failure.
+///
+/// Unlike [`ParseError`], which only reports that parsing failed, this type
+/// reports why ([`NsidError::kind`]) and where ([`NsidError::position`], a
+/// byte offset into the original input).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NsidError {
+    kind: NsidErrorKind,
+    position: usize,
+}
+
+impl NsidError {
+    #[inline]
+    fn new(kind: NsidErrorKind, position: usize) -> Self {
+        NsidError { kind, position }
+    }
+
+    /// The reason validation failed.
+    #[inline]
+    pub fn kind(&self) -> NsidErrorKind {
+        self.kind
+    }
+
+    /// The byte offset within the input at which validation failed.
+    ///
+    /// This is the start of the offending segment, except for
+    /// [`NsidErrorKind::NonAsciiAlphanumeric`], where it is the offending
+    /// character, and for [`NsidErrorKind::TooLong`] and
+    /// [`NsidErrorKind::TooFewSegments`], where it is always `0`.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for NsidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            NsidErrorKind::TooLong => {
+                write!(f, "NSID exceeds maximum length of {MAX_LEN} bytes")
+            }
+            NsidErrorKind::TooFewSegments => {
+                write!(f, "NSID has fewer than {MIN_SEGMENTS} segments")
+            }
+            NsidErrorKind::InvalidTld => {
+                write!(f, "invalid top-level domain at byte offset {}", self.position)
+            }
+            NsidErrorKind::InvalidAuthoritySegment => write!(
+                f,
+                "invalid authority segment at byte offset {}",
+                self.position
+            ),
+            NsidErrorKind::AuthorityTooLong => write!(
+                f,
+                "authority is {MAX_AUTHORITY_LEN} bytes or longer at byte offset {}",
+                self.position
+            ),
+            NsidErrorKind::InvalidNameSegment => {
+                write!(f, "invalid name segment at byte offset {}", self.position)
+            }
+            NsidErrorKind::NonAsciiAlphanumeric => write!(
+                f,
+                "non-alphanumeric character at byte offset {}",
+                self.position
+            ),
+            NsidErrorKind::MissingFragmentPrefix => f.write_str("fragment is missing leading '#'"),
+            NsidErrorKind::InvalidFragmentLength => {
+                write!(f, "fragment length is out of range at byte offset {}", self.position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for NsidError {}
+
+impl From<NsidError> for ParseError {
+    fn from(err: NsidError) -> Self {
+        match err.kind {
+            NsidErrorKind::NonAsciiAlphanumeric
+            | NsidErrorKind::MissingFragmentPrefix
+            | NsidErrorKind::InvalidFragmentLength => ParseError::nsid_fragment(),
+            _ => ParseError::nsid(),
+        }
+    }
+}
+
+fn validate_nsid(bytes: &[u8]) -> Result<(), NsidError> {
     if bytes.len() > MAX_LEN {
-        return Err(ParseError::nsid());
+        return Err(NsidError::new(NsidErrorKind::TooLong, 0));
     }
 
     let mut it = bytes.split(|&b| b == b'.').peekable();
 
-    let tld = it.next().ok_or_else(ParseError::nsid)?;
+    let tld = it
+        .next()
+        .ok_or(NsidError::new(NsidErrorKind::TooFewSegments, 0))?;
 
     if !is_valid_tld(tld) {
-        return Err(ParseError::nsid());
+        return Err(NsidError::new(NsidErrorKind::InvalidTld, 0));
     }
 
     let mut len = tld.len();
+    let mut pos = tld.len();
     let mut num_segments = 1;
     while let Some(segment) = it.next() {
-        let is_valid = match it.peek() {
-            Some(_) => is_valid_domain_segment(segment),
-            None => len < MAX_AUTHORITY_LEN && is_valid_nsid_name(segment),
+        let seg_start = pos + 1;
+        let is_last = it.peek().is_none();
+
+        let is_valid = if is_last {
+            len < MAX_AUTHORITY_LEN && is_valid_nsid_name(segment)
+        } else {
+            is_valid_domain_segment(segment)
         };
+        let authority_len = len;
 
         num_segments += 1;
         len += 1 + segment.len();
+        pos = seg_start + segment.len();
 
         if !is_valid {
-            return Err(ParseError::nsid());
+            let kind = if !is_last {
+                NsidErrorKind::InvalidAuthoritySegment
+            } else if authority_len >= MAX_AUTHORITY_LEN {
+                NsidErrorKind::AuthorityTooLong
+            } else {
+                NsidErrorKind::InvalidNameSegment
+            };
+            return Err(NsidError::new(kind, seg_start));
         }
     }
 
     if num_segments < MIN_SEGMENTS {
-        return Err(ParseError::nsid());
+        return Err(NsidError::new(NsidErrorKind::TooFewSegments, 0));
     }
 
     Ok(())
@@ -89,27 +440,35 @@ impl Fragment {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Parses `s`, returning a structured [`NsidError`] on failure instead
+    /// of the opaque [`ParseError`] returned by [`FromStr::from_str`].
+    pub fn parse_detailed(s: &str) -> Result<Fragment, NsidError> {
+        validate_fragment(s.as_bytes()).map(|()| Fragment(s.into()))
+    }
 }
 
 impl FromStr for Fragment {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        validate_fragment(s.as_bytes()).map(|()| Fragment(s.into()))
+        validate_fragment(s.as_bytes())
+            .map(|()| Fragment(s.into()))
+            .map_err(ParseError::from)
     }
 }
 
-fn validate_fragment(bytes: &[u8]) -> Result<(), ParseError> {
-    let bytes = bytes
+fn validate_fragment(bytes: &[u8]) -> Result<(), NsidError> {
+    let rest = bytes
         .strip_prefix(b"#")
-        .ok_or_else(ParseError::nsid_fragment)?;
+        .ok_or(NsidError::new(NsidErrorKind::MissingFragmentPrefix, 0))?;
 
-    if !SEGMENT_LEN_RANGE.contains(&bytes.len()) {
-        return Err(ParseError::nsid_fragment());
+    if !SEGMENT_LEN_RANGE.contains(&rest.len()) {
+        return Err(NsidError::new(NsidErrorKind::InvalidFragmentLength, 1));
     }
 
-    if !bytes.iter().all(|c| c.is_ascii_alphanumeric()) {
-        return Err(ParseError::nsid_fragment());
+    if let Some(offset) = rest.iter().position(|c| !c.is_ascii_alphanumeric()) {
+        return Err(NsidError::new(NsidErrorKind::NonAsciiAlphanumeric, 1 + offset));
     }
 
     Ok(())
@@ -122,6 +481,39 @@ impl fmt::Display for Fragment {
     }
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<String> for Fragment {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        validate_fragment(s.as_bytes())
+            .map(|()| Fragment(s))
+            .map_err(ParseError::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Fragment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Fragment {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Fragment::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
 /// An NSID reference.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Reference {
@@ -152,6 +544,40 @@ impl fmt::Display for Reference {
     }
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<String> for Reference {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Reference::from_str(&s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Reference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Reference::Full(r) => serializer.serialize_str(r.text.as_str()),
+            Reference::Relative(r) => serializer.serialize_str(r.as_str()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Reference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Reference::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 /// A fully-qualified NSID reference.
 ///
 /// This consists of an NSID and an optional fragment.
@@ -162,8 +588,27 @@ pub struct FullReference {
 }
 
 impl FullReference {
+    /// Borrows this reference as a zero-copy [`FullReferenceRef`].
+    #[inline]
+    pub fn as_ref(&self) -> FullReferenceRef<'_> {
+        FullReferenceRef {
+            text: self.text.as_str(),
+            frag_start: self.frag_start,
+        }
+    }
+
     pub fn clone_nsid(&self) -> Nsid {
-        Nsid(self.text[..self.frag_start].to_string())
+        self.as_ref().nsid().to_owned()
+    }
+
+    /// Resolves a relative [`Fragment`] against a base [`Nsid`], producing
+    /// the equivalent [`FullReference`] without any string re-parsing.
+    pub fn with_fragment(nsid: Nsid, frag: &Fragment) -> FullReference {
+        let frag_start = nsid.0.len();
+        let mut text = nsid.0;
+        text.push_str(frag.as_str());
+
+        FullReference { text, frag_start }
     }
 
     #[inline]
@@ -177,25 +622,53 @@ impl FullReference {
     }
 
     pub fn fragment_name(&self) -> Option<&str> {
-        self.has_fragment()
-            .then_some(&self.text[self.frag_start + 1..])
+        self.as_ref().fragment_name()
     }
 }
 
-impl From<Nsid> for FullReference {
+/// A borrowed, validated [`FullReference`].
+///
+/// Like [`NsidRef`], this runs the same validation as `FullReference`'s
+/// `FromStr` impl but borrows its input rather than allocating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FullReferenceRef<'a> {
+    text: &'a str,
+    frag_start: usize,
+}
+
+impl<'a> FullReferenceRef<'a> {
     #[inline]
-    fn from(nsid: Nsid) -> Self {
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn nsid(&self) -> NsidRef<'a> {
+        NsidRef(&self.text[..self.frag_start])
+    }
+
+    #[inline]
+    fn has_fragment(&self) -> bool {
+        self.frag_start < self.text.len()
+    }
+
+    pub fn fragment_name(&self) -> Option<&'a str> {
+        self.has_fragment()
+            .then_some(&self.text[self.frag_start + 1..])
+    }
+
+    /// Allocates an owned [`FullReference`] with the same contents.
+    pub fn to_owned(&self) -> FullReference {
         FullReference {
-            frag_start: nsid.0.len(),
-            text: nsid.0,
+            text: self.text.to_string(),
+            frag_start: self.frag_start,
         }
     }
 }
 
-impl FromStr for FullReference {
-    type Err = ParseError;
+impl<'a> TryFrom<&'a str> for FullReferenceRef<'a> {
+    type Error = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         let frag_start = s.find('#').unwrap_or(s.len());
         let (nsid_s, frag_s) = s.split_at(frag_start);
 
@@ -205,10 +678,33 @@ impl FromStr for FullReference {
             validate_fragment(frag_s.as_bytes())?;
         }
 
-        Ok(FullReference {
-            text: s.into(),
-            frag_start,
-        })
+        Ok(FullReferenceRef { text: s, frag_start })
+    }
+}
+
+impl fmt::Display for FullReferenceRef<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+impl From<Nsid> for FullReference {
+    #[inline]
+    fn from(nsid: Nsid) -> Self {
+        FullReference {
+            frag_start: nsid.0.len(),
+            text: nsid.0,
+        }
+    }
+}
+
+impl FromStr for FullReference {
+    type Err = ParseError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FullReferenceRef::try_from(s).map(|r| r.to_owned())
     }
 }
 
@@ -219,6 +715,38 @@ impl fmt::Display for FullReference {
     }
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<String> for FullReference {
+    type Error = ParseError;
+
+    #[inline]
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let frag_start = FullReferenceRef::try_from(s.as_str())?.frag_start;
+        Ok(FullReference { text: s, frag_start })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for FullReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.text.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FullReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        FullReference::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,4 +766,180 @@ mod tests {
     fn invalid_examples() {
         crate::test::test_invalid::<Nsid>(["com.exa🤯ple.thing", "com.example"]);
     }
+
+    #[test]
+    fn nsid_ref_round_trip() {
+        let owned = Nsid::from_str("com.example.fooBar").unwrap();
+        let borrowed = NsidRef::try_from("com.example.fooBar").unwrap();
+
+        assert_eq!(owned.as_str(), borrowed.as_str());
+        assert_eq!(owned, borrowed.to_owned());
+        assert_eq!(owned.as_ref(), borrowed);
+    }
+
+    #[test]
+    fn nsid_ref_rejects_invalid() {
+        assert!(NsidRef::try_from("com.example").is_err());
+    }
+
+    #[test]
+    fn full_reference_ref_round_trip() {
+        let owned = FullReference::from_str("com.example.fooBar#main").unwrap();
+        let borrowed = FullReferenceRef::try_from("com.example.fooBar#main").unwrap();
+
+        assert_eq!(borrowed.nsid().as_str(), "com.example.fooBar");
+        assert_eq!(borrowed.fragment_name(), Some("main"));
+        assert_eq!(owned, borrowed.to_owned());
+        assert_eq!(owned.as_ref(), borrowed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn nsid_serde_round_trip() {
+        let nsid = Nsid::from_str("com.example.fooBar").unwrap();
+        let json = serde_json::to_string(&nsid).unwrap();
+        assert_eq!(json, "\"com.example.fooBar\"");
+        assert_eq!(serde_json::from_str::<Nsid>(&json).unwrap(), nsid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn nsid_serde_rejects_invalid() {
+        assert!(serde_json::from_str::<Nsid>("\"com.example\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn full_reference_serde_round_trip() {
+        let r = FullReference::from_str("com.example.fooBar#main").unwrap();
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(serde_json::from_str::<FullReference>(&json).unwrap(), r);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fragment_serde_round_trip() {
+        let frag = Fragment::from_str("#main").unwrap();
+        let json = serde_json::to_string(&frag).unwrap();
+        assert_eq!(json, "\"#main\"");
+        assert_eq!(serde_json::from_str::<Fragment>(&json).unwrap(), frag);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn fragment_serde_rejects_invalid() {
+        assert!(serde_json::from_str::<Fragment>("\"main\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn reference_serde_round_trip() {
+        let full = Reference::from_str("com.example.fooBar#main").unwrap();
+        let json = serde_json::to_string(&full).unwrap();
+        assert_eq!(serde_json::from_str::<Reference>(&json).unwrap(), full);
+
+        let relative = Reference::from_str("#main").unwrap();
+        let json = serde_json::to_string(&relative).unwrap();
+        assert_eq!(serde_json::from_str::<Reference>(&json).unwrap(), relative);
+    }
+
+    #[test]
+    fn nsid_error_reports_reason_and_position() {
+        let err = Nsid::parse_detailed("com.exa🤯ple.thing").unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::InvalidAuthoritySegment);
+        assert_eq!(err.position(), 4);
+
+        let err = Nsid::parse_detailed("com.example").unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::TooFewSegments);
+
+        let err = Nsid::parse_detailed("a.b.c-🤯").unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::InvalidNameSegment);
+        assert_eq!(err.position(), 4);
+    }
+
+    #[test]
+    fn nsid_error_long_valid_authority_reports_invalid_name() {
+        // Authority is 250 bytes, just under `MAX_AUTHORITY_LEN` (253), so it
+        // is valid on its own; only the final (name) segment is malformed.
+        // The reported reason must reflect the name failure, not the
+        // authority length, even though appending the bad name segment
+        // pushes the running `len` past `MAX_AUTHORITY_LEN`.
+        let seg = "a".repeat(63);
+        let input = format!("com.{seg}.{seg}.{seg}.{}.🤯", &seg[..54]);
+
+        let err = Nsid::parse_detailed(&input).unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::InvalidNameSegment);
+    }
+
+    #[test]
+    fn fragment_error_reports_reason_and_position() {
+        let err = Fragment::parse_detailed("main").unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::MissingFragmentPrefix);
+
+        let err = Fragment::parse_detailed("#ma🤯in").unwrap_err();
+        assert_eq!(err.kind(), NsidErrorKind::NonAsciiAlphanumeric);
+        assert_eq!(err.position(), 3);
+    }
+
+    #[test]
+    fn authority_and_name() {
+        let nsid = Nsid::from_str("com.example.fooBar").unwrap();
+        assert_eq!(nsid.authority(), "com.example");
+        assert_eq!(nsid.name(), "fooBar");
+        assert_eq!(nsid.domain_authority(), "example.com");
+    }
+
+    #[test]
+    fn from_parts_round_trips() {
+        let nsid = Nsid::from_parts("com.example", "fooBar").unwrap();
+        assert_eq!(nsid.as_str(), "com.example.fooBar");
+
+        assert!(Nsid::from_parts("com.example", "🤯").is_err());
+    }
+
+    #[test]
+    fn from_parts_rejects_multi_segment_name() {
+        assert!(Nsid::from_parts("com", "example.fooBar").is_err());
+    }
+
+    #[test]
+    fn with_fragment_builds_full_reference() {
+        let nsid = Nsid::from_str("com.example.fooBar").unwrap();
+        let frag = Fragment::from_str("#main").unwrap();
+
+        let full = FullReference::with_fragment(nsid, &frag);
+        assert_eq!(full.to_string(), "com.example.fooBar#main");
+        assert_eq!(full.fragment_name(), Some("main"));
+    }
+
+    #[test]
+    fn normalize_lowercases_authority_only() {
+        let nsid = Nsid::from_str("Com.Example.fooBar").unwrap();
+        let normalized = nsid.normalize();
+
+        assert_eq!(normalized.as_str(), "com.example.fooBar");
+    }
+
+    #[test]
+    fn canonical_eq_ignores_authority_case() {
+        let a = Nsid::from_str("com.example.fooBar").unwrap();
+        let b = Nsid::from_str("Com.Example.fooBar").unwrap();
+        let c = Nsid::from_str("com.example.FooBar").unwrap();
+
+        assert_ne!(a, b);
+        assert!(a.canonical_eq(&b));
+        assert!(!a.canonical_eq(&c));
+    }
+
+    #[test]
+    fn canonical_nsid_dedups_by_authority_case() {
+        use std::collections::HashSet;
+
+        let a = Nsid::from_str("com.example.fooBar").unwrap();
+        let b = Nsid::from_str("COM.EXAMPLE.fooBar").unwrap();
+
+        let mut set = HashSet::new();
+        set.insert(CanonicalNsid::from(a));
+        assert!(!set.insert(CanonicalNsid::from(b)));
+    }
 }